@@ -0,0 +1,194 @@
+use crate::{
+	storage::chunk::{
+		RawChunk,
+		error::{ChunkError, Result},
+	},
+};
+use std::collections::HashMap;
+
+/// The cached state of a single cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CacheEntry {
+	/// The bytes last observed in or written back to storage.
+	///
+	/// `None` if the cell has never been loaded from or flushed to
+	/// the underlying chunk, in which case its true storage state is
+	/// unknown and a dirty entry must always be written back.
+	original: Option<Option<Vec<u8>>>,
+	/// The bytes as currently cached.
+	current: Option<Vec<u8>>,
+	/// `true` if `current` has not yet been written back to storage.
+	dirty: bool,
+}
+
+/// A write-back cache over a `RawChunk`.
+///
+/// Memoizes the bytes of every loaded or stored cell in an in-memory
+/// map keyed by cell index. Once a cell has been loaded, further loads
+/// are served from the cache without touching contract storage; stores
+/// only update the cache and are buffered until `flush` is called.
+///
+/// # Guarantees
+///
+/// - `Owned`
+///
+/// Read more about kinds of guarantees and their effect [here](../../index.html#guarantees).
+#[derive(Debug)]
+pub struct CachedChunk {
+	/// The underlying raw chunk.
+	chunk: RawChunk,
+	/// The in-memory cache of cell contents, keyed by cell index.
+	cache: HashMap<u32, CacheEntry>,
+}
+
+impl CachedChunk {
+	/// Creates a new cached chunk on top of the given raw chunk.
+	pub fn new(chunk: RawChunk) -> Self {
+		Self{
+			chunk,
+			cache: HashMap::new(),
+		}
+	}
+
+	/// Returns the capacity of this chunk.
+	pub fn capacity(&self) -> u32 {
+		self.chunk.capacity()
+	}
+
+	/// Returns an error if `n` is not within bounds.
+	fn check_bounds(&self, n: u32) -> Result<()> {
+		if n >= self.capacity() {
+			return Err(ChunkError::access_out_of_bounds(n, self.capacity()))
+		}
+		Ok(())
+	}
+
+	/// Loads the bytes stored in the `n`-th cell.
+	///
+	/// The first load for a given cell calls through to the underlying
+	/// chunk and populates the cache entry; subsequent loads return the
+	/// cached bytes with zero storage reads.
+	pub fn load(&mut self, n: u32) -> Result<Option<Vec<u8>>> {
+		if !self.cache.contains_key(&n) {
+			let value = self.chunk.load(n)?;
+			self.cache.insert(n, CacheEntry{
+				original: Some(value.clone()),
+				current: value,
+				dirty: false,
+			});
+		}
+		Ok(self.cache[&n].current.clone())
+	}
+
+	/// Stores the given bytes into the `n`-th cell.
+	///
+	/// The bytes are only buffered in the cache and marked dirty; call
+	/// `flush` to write dirty entries back to the underlying chunk.
+	pub fn store(&mut self, n: u32, bytes: &[u8]) -> Result<()> {
+		self.check_bounds(n)?;
+		self.update(n, Some(bytes.to_vec()));
+		Ok(())
+	}
+
+	/// Removes the bytes stored in the `n`-th cell.
+	///
+	/// Marks the entry dirty-empty; the removal is written back to the
+	/// underlying chunk on the next `flush`.
+	pub fn clear(&mut self, n: u32) -> Result<()> {
+		self.check_bounds(n)?;
+		self.update(n, None);
+		Ok(())
+	}
+
+	/// Updates the cached value of the `n`-th cell and marks it dirty.
+	fn update(&mut self, n: u32, value: Option<Vec<u8>>) {
+		self.cache
+			.entry(n)
+			.and_modify(|entry| entry.current = value.clone())
+			.or_insert(CacheEntry{
+				original: None,
+				current: value,
+				dirty: false,
+			})
+			.dirty = true;
+	}
+
+	/// Writes all dirty cache entries back to the underlying chunk.
+	///
+	/// A dirty entry whose bytes are identical to what was last loaded
+	/// from storage is skipped, avoiding a redundant `ContractEnv::store`.
+	pub fn flush(&mut self) -> Result<()> {
+		for (&n, entry) in self.cache.iter_mut() {
+			if !entry.dirty {
+				continue
+			}
+			let unchanged = entry.original.as_ref() == Some(&entry.current);
+			if !unchanged {
+				match &entry.current {
+					Some(bytes) => self.chunk.store(n, bytes)?,
+					None => self.chunk.clear(n)?,
+				}
+			}
+			entry.original = Some(entry.current.clone());
+			entry.dirty = false;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(all(test, feature = "test-env"))]
+mod tests {
+	use super::*;
+
+	use crate::{
+		env::TestEnv,
+		storage::Key,
+	};
+
+	fn new_chunk(capacity: u32) -> CachedChunk {
+		CachedChunk::new(unsafe {
+			RawChunk::new_unchecked(Key([0x42; 32]), capacity)
+		})
+	}
+
+	#[test]
+	fn repeated_load_hits_cache_once() {
+		const CAPACITY: u32 = 5;
+
+		let mut chunk = new_chunk(CAPACITY);
+
+		assert_eq!(TestEnv::total_reads(), 0);
+		for _ in 0..3 {
+			assert_eq!(chunk.load(0), Ok(None));
+		}
+		// Only the very first load should have touched storage.
+		assert_eq!(TestEnv::total_reads(), 1);
+	}
+
+	#[test]
+	fn flush_skips_unchanged_and_writes_once() {
+		const CAPACITY: u32 = 5;
+
+		let mut chunk = new_chunk(CAPACITY);
+
+		// Loading then storing back the same bytes should flush to nothing.
+		assert_eq!(chunk.load(0), Ok(None));
+		chunk.clear(0).unwrap();
+		chunk.flush().unwrap();
+		assert_eq!(TestEnv::total_writes(), 0);
+
+		// A genuine change is written back exactly once per flush.
+		chunk.store(1, b"test").unwrap();
+		chunk.store(1, b"test").unwrap();
+		chunk.flush().unwrap();
+		assert_eq!(TestEnv::total_writes(), 1);
+
+		// Flushing again with no further changes writes nothing more.
+		chunk.flush().unwrap();
+		assert_eq!(TestEnv::total_writes(), 1);
+
+		// A fresh chunk over the same key observes the flushed state.
+		let mut fresh = new_chunk(CAPACITY);
+		assert_eq!(fresh.load(1), Ok(Some(b"test".to_vec())));
+	}
+}
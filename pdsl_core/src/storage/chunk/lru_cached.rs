@@ -0,0 +1,301 @@
+use crate::{
+	storage::chunk::{
+		RawChunk,
+		error::{ChunkError, Result},
+	},
+};
+use std::collections::HashMap;
+
+/// Sentinel cell index meaning "no cell", used to terminate the
+/// intrusive recency list.
+const NONE: u32 = u32::MAX;
+
+/// A single LRU-tracked cache entry.
+///
+/// `prev`/`next` thread the entry into the intrusive doubly-linked
+/// recency list, avoiding a second allocation for the list itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LruEntry {
+	/// The cached bytes of the cell.
+	value: Option<Vec<u8>>,
+	/// `true` if `value` has not yet been written back to storage.
+	dirty: bool,
+	/// The cell index that is more recently used than this one, or `NONE`.
+	prev: u32,
+	/// The cell index that is less recently used than this one, or `NONE`.
+	next: u32,
+}
+
+/// An LRU-bounded write-back cache over a `RawChunk`.
+///
+/// Like `CachedChunk`, but keeps only the `capacity` hottest cells
+/// resident in memory. When inserting a new entry would exceed
+/// `capacity`, the least-recently-used entry is evicted, writing it
+/// back to storage first if it is dirty. This lets a contract iterate
+/// over a chunk far larger than the resident cache without losing any
+/// pending writes.
+///
+/// # Guarantees
+///
+/// - `Owned`
+///
+/// Read more about kinds of guarantees and their effect [here](../../index.html#guarantees).
+#[derive(Debug)]
+pub struct LruCachedChunk {
+	/// The underlying raw chunk.
+	chunk: RawChunk,
+	/// The maximum number of cells kept resident in `cache`.
+	capacity: usize,
+	/// The resident cells, keyed by cell index.
+	cache: HashMap<u32, LruEntry>,
+	/// The most-recently-used cell index, or `NONE` if empty.
+	head: u32,
+	/// The least-recently-used cell index, or `NONE` if empty.
+	tail: u32,
+}
+
+impl LruCachedChunk {
+	/// Creates a new LRU-bounded cached chunk on top of the given raw
+	/// chunk, resident cache limited to `capacity` cells.
+	///
+	/// A `capacity` of `0` disables caching entirely: every `load`,
+	/// `store` and `clear` passes straight through to the underlying
+	/// chunk instead of panicking on an empty recency list.
+	pub fn new(chunk: RawChunk, capacity: usize) -> Self {
+		Self{
+			chunk,
+			capacity,
+			cache: HashMap::new(),
+			head: NONE,
+			tail: NONE,
+		}
+	}
+
+	/// Returns the capacity of the underlying chunk.
+	pub fn capacity(&self) -> u32 {
+		self.chunk.capacity()
+	}
+
+	/// Returns an error if `n` is not within bounds.
+	fn check_bounds(&self, n: u32) -> Result<()> {
+		if n >= self.capacity() {
+			return Err(ChunkError::access_out_of_bounds(n, self.capacity()))
+		}
+		Ok(())
+	}
+
+	/// Removes `n` from the recency list without removing it from `cache`.
+	///
+	/// Safe to call on a node that is not currently linked.
+	fn unlink(&mut self, n: u32) {
+		let (prev, next) = {
+			let entry = &self.cache[&n];
+			(entry.prev, entry.next)
+		};
+		if prev != NONE {
+			self.cache.get_mut(&prev).unwrap().next = next;
+		} else if self.head == n {
+			self.head = next;
+		}
+		if next != NONE {
+			self.cache.get_mut(&next).unwrap().prev = prev;
+		} else if self.tail == n {
+			self.tail = prev;
+		}
+	}
+
+	/// Inserts `n` at the most-recently-used end of the recency list.
+	fn push_front(&mut self, n: u32) {
+		let old_head = self.head;
+		{
+			let entry = self.cache.get_mut(&n).unwrap();
+			entry.prev = NONE;
+			entry.next = old_head;
+		}
+		if old_head != NONE {
+			self.cache.get_mut(&old_head).unwrap().prev = n;
+		}
+		self.head = n;
+		if self.tail == NONE {
+			self.tail = n;
+		}
+	}
+
+	/// Moves `n` to the most-recently-used end of the recency list.
+	fn touch(&mut self, n: u32) {
+		if self.head == n {
+			return
+		}
+		self.unlink(n);
+		self.push_front(n);
+	}
+
+	/// Evicts the least-recently-used entry, writing it back first if dirty.
+	fn evict_lru(&mut self) -> Result<()> {
+		let lru = self.tail;
+		self.unlink(lru);
+		if let Some(entry) = self.cache.remove(&lru) {
+			if entry.dirty {
+				match entry.value {
+					Some(bytes) => self.chunk.store(lru, &bytes)?,
+					None => self.chunk.clear(lru)?,
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Inserts a freshly-observed cell, evicting the LRU entry first if
+	/// the cache is already at capacity.
+	fn insert_new(&mut self, n: u32, value: Option<Vec<u8>>, dirty: bool) -> Result<()> {
+		if self.cache.len() >= self.capacity {
+			self.evict_lru()?;
+		}
+		self.cache.insert(n, LruEntry{ value, dirty, prev: NONE, next: NONE });
+		self.push_front(n);
+		Ok(())
+	}
+
+	/// Loads the bytes stored in the `n`-th cell.
+	///
+	/// Touches `n` to the most-recently-used position; if `n` is not
+	/// already resident, loads it through the underlying chunk first,
+	/// possibly evicting the current least-recently-used cell.
+	pub fn load(&mut self, n: u32) -> Result<Option<Vec<u8>>> {
+		self.check_bounds(n)?;
+		if self.capacity == 0 {
+			return self.chunk.load(n)
+		}
+		if self.cache.contains_key(&n) {
+			self.touch(n);
+		} else {
+			let value = self.chunk.load(n)?;
+			self.insert_new(n, value, false)?;
+		}
+		Ok(self.cache[&n].value.clone())
+	}
+
+	/// Stores the given bytes into the `n`-th cell.
+	pub fn store(&mut self, n: u32, bytes: &[u8]) -> Result<()> {
+		self.check_bounds(n)?;
+		if self.capacity == 0 {
+			return self.chunk.store(n, bytes)
+		}
+		if self.cache.contains_key(&n) {
+			{
+				let entry = self.cache.get_mut(&n).unwrap();
+				entry.value = Some(bytes.to_vec());
+				entry.dirty = true;
+			}
+			self.touch(n);
+		} else {
+			self.insert_new(n, Some(bytes.to_vec()), true)?;
+		}
+		Ok(())
+	}
+
+	/// Removes the bytes stored in the `n`-th cell.
+	pub fn clear(&mut self, n: u32) -> Result<()> {
+		self.check_bounds(n)?;
+		if self.capacity == 0 {
+			return self.chunk.clear(n)
+		}
+		if self.cache.contains_key(&n) {
+			{
+				let entry = self.cache.get_mut(&n).unwrap();
+				entry.value = None;
+				entry.dirty = true;
+			}
+			self.touch(n);
+		} else {
+			self.insert_new(n, None, true)?;
+		}
+		Ok(())
+	}
+
+	/// Writes all resident dirty entries back to the underlying chunk.
+	pub fn flush(&mut self) -> Result<()> {
+		let dirty: Vec<u32> = self.cache
+			.iter()
+			.filter(|(_, entry)| entry.dirty)
+			.map(|(&n, _)| n)
+			.collect();
+		for n in dirty {
+			let value = self.cache[&n].value.clone();
+			match value {
+				Some(bytes) => self.chunk.store(n, &bytes)?,
+				None => self.chunk.clear(n)?,
+			}
+			self.cache.get_mut(&n).unwrap().dirty = false;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(all(test, feature = "test-env"))]
+mod tests {
+	use super::*;
+
+	use crate::{
+		env::TestEnv,
+		storage::Key,
+	};
+
+	fn new_chunk(capacity: u32, lru_capacity: usize) -> LruCachedChunk {
+		LruCachedChunk::new(
+			unsafe { RawChunk::new_unchecked(Key([0x42; 32]), capacity) },
+			lru_capacity,
+		)
+	}
+
+	#[test]
+	fn resident_set_never_exceeds_capacity() {
+		const CAPACITY: u32 = 10;
+		const LRU_CAPACITY: usize = 3;
+
+		let mut chunk = new_chunk(CAPACITY, LRU_CAPACITY);
+
+		for i in 0..CAPACITY {
+			chunk.load(i).unwrap();
+			assert!(chunk.cache.len() <= LRU_CAPACITY);
+		}
+		assert_eq!(chunk.cache.len(), LRU_CAPACITY);
+	}
+
+	#[test]
+	fn eviction_writes_back_dirty_entries() {
+		const CAPACITY: u32 = 10;
+		const LRU_CAPACITY: usize = 2;
+
+		let mut chunk = new_chunk(CAPACITY, LRU_CAPACITY);
+
+		chunk.store(0, b"first").unwrap();
+		chunk.store(1, b"second").unwrap();
+		assert_eq!(TestEnv::total_writes(), 0);
+
+		// Touching a third distinct cell evicts cell `0`, the LRU entry,
+		// flushing its pending write before it is dropped.
+		chunk.load(2).unwrap();
+		assert_eq!(TestEnv::total_writes(), 1);
+
+		// A fresh chunk over the same key observes the evicted write.
+		let mut fresh = new_chunk(CAPACITY, LRU_CAPACITY);
+		assert_eq!(fresh.load(0), Ok(Some(b"first".to_vec())));
+	}
+
+	#[test]
+	fn zero_capacity_bypasses_caching() {
+		const CAPACITY: u32 = 5;
+
+		let mut chunk = new_chunk(CAPACITY, 0);
+
+		// Nothing is ever kept resident, so every access passes straight
+		// through to the underlying chunk instead of panicking.
+		assert_eq!(chunk.load(0), Ok(None));
+		chunk.store(0, b"test").unwrap();
+		assert_eq!(chunk.load(0), Ok(Some(b"test".to_vec())));
+		chunk.clear(0).unwrap();
+		assert_eq!(chunk.load(0), Ok(None));
+		assert!(chunk.cache.is_empty());
+	}
+}
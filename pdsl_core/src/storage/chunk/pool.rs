@@ -0,0 +1,248 @@
+use crate::{
+	storage::chunk::{
+		RawChunk,
+		error::{ChunkError, Result},
+	},
+};
+
+/// Sentinel free-list index meaning "no cell", i.e. an empty free list
+/// or the end of the chain of freed cells.
+const EMPTY: u32 = u32::MAX;
+
+/// The pool's header, persisted in cell `0` of the underlying chunk.
+///
+/// `head` is the index of the most recently freed cell, forming the
+/// top of a singly-linked stack threaded through the free cells'
+/// storage; `watermark` is the index of the next never-yet-allocated
+/// cell.
+struct Header {
+	watermark: u32,
+	head: u32,
+}
+
+impl Header {
+	/// The header a pool has never been allocated from, i.e. an empty
+	/// free list and a watermark right after the reserved header cell.
+	fn fresh() -> Self {
+		Self{ watermark: 1, head: EMPTY }
+	}
+
+	fn encode(&self) -> [u8; 8] {
+		let mut bytes = [0_u8; 8];
+		bytes[0..4].copy_from_slice(&self.watermark.to_be_bytes());
+		bytes[4..8].copy_from_slice(&self.head.to_be_bytes());
+		bytes
+	}
+
+	/// Decodes a header from its persisted bytes.
+	///
+	/// Returns a `ChunkError` instead of panicking if `bytes` is
+	/// shorter than the encoded header, e.g. because cell `0` holds
+	/// foreign data from before it became a pool's header cell.
+	fn decode(bytes: &[u8]) -> Result<Self> {
+		const ENCODED_LEN: u32 = 8;
+		if bytes.len() < ENCODED_LEN as usize {
+			return Err(ChunkError::access_out_of_bounds(bytes.len() as u32, ENCODED_LEN))
+		}
+		let mut watermark = [0_u8; 4];
+		let mut head = [0_u8; 4];
+		watermark.copy_from_slice(&bytes[0..4]);
+		head.copy_from_slice(&bytes[4..8]);
+		Ok(Self{
+			watermark: u32::from_be_bytes(watermark),
+			head: u32::from_be_bytes(head),
+		})
+	}
+}
+
+/// A free-list allocator of individual cells within a `RawChunk`.
+///
+/// Turns a `RawChunk` into a pool that hands out and reclaims cell
+/// indices, analogous to an intrusive free-list memory pool. Cell `0`
+/// is reserved for the pool's header; every freed cell stores the
+/// index of the next freed cell in its own bytes, so the free list
+/// costs no storage beyond the cells it tracks.
+///
+/// # Guarantees
+///
+/// - `Owned`
+///
+/// Read more about kinds of guarantees and their effect [here](../../index.html#guarantees).
+pub struct ChunkPool {
+	/// The underlying raw chunk; cell `0` is reserved for the header.
+	chunk: RawChunk,
+}
+
+impl ChunkPool {
+	/// Creates a new cell pool on top of the given raw chunk.
+	///
+	/// The header is initialized lazily on first use, so this never
+	/// touches storage by itself.
+	pub fn new(chunk: RawChunk) -> Self {
+		Self{ chunk }
+	}
+
+	/// Reads the pool's header, defaulting to a fresh header if the
+	/// pool has never been allocated from.
+	fn read_header(&self) -> Result<Header> {
+		match self.chunk.load(0).expect("cell 0 is always in bounds") {
+			Some(bytes) => Header::decode(&bytes),
+			None => Ok(Header::fresh()),
+		}
+	}
+
+	/// Writes the pool's header back to cell `0`.
+	fn write_header(&mut self, header: &Header) -> Result<()> {
+		self.chunk.store(0, &header.encode())
+	}
+
+	/// Allocates and returns a previously unused cell index.
+	///
+	/// Pops the head of the free list if non-empty; otherwise grows
+	/// the pool from its high-water mark. Returns a `ChunkError` if the
+	/// underlying chunk's capacity is exhausted.
+	pub fn alloc(&mut self) -> Result<u32> {
+		let mut header = self.read_header()?;
+
+		if header.head != EMPTY {
+			let freed = header.head;
+			let next = self.chunk
+				.load(freed)
+				.expect("freed cells are always in bounds")
+				.map(|bytes| {
+					let mut buf = [0_u8; 4];
+					buf.copy_from_slice(&bytes[0..4]);
+					u32::from_be_bytes(buf)
+				})
+				.unwrap_or(EMPTY);
+			self.chunk.clear(freed).expect("freed cells are always in bounds");
+			header.head = next;
+			self.write_header(&header)?;
+			return Ok(freed)
+		}
+
+		if header.watermark >= self.chunk.capacity() {
+			return Err(ChunkError::access_out_of_bounds(header.watermark, self.chunk.capacity()))
+		}
+		let allocated = header.watermark;
+		header.watermark += 1;
+		self.write_header(&header)?;
+		Ok(allocated)
+	}
+
+	/// Returns the given cell index to the pool for future allocation.
+	///
+	/// # Safety
+	///
+	/// The pool tracks liveness nowhere but the free list itself, so it
+	/// cannot detect every double free: the caller must ensure `n` was
+	/// obtained from a prior `alloc` call and has not already been
+	/// freed since. Violating this splices `n` into the free list a
+	/// second time, and a later `alloc` hands the same cell out to two
+	/// live allocations at once, silently aliasing their storage.
+	///
+	/// The two cheapest and most common mistakes are still rejected
+	/// mechanically rather than left to this invariant: freeing `0`,
+	/// the cell reserved for the pool's own header (`alloc` never hands
+	/// it out), and re-freeing the cell currently at the head of the
+	/// free list, i.e. an immediate double free.
+	pub unsafe fn free(&mut self, n: u32) -> Result<()> {
+		let mut header = self.read_header()?;
+		if n == 0 || n == header.head {
+			return Err(ChunkError::access_out_of_bounds(n, self.chunk.capacity()))
+		}
+		self.chunk.store(n, &header.head.to_be_bytes())?;
+		header.head = n;
+		self.write_header(&header)?;
+		Ok(())
+	}
+}
+
+#[cfg(all(test, feature = "test-env"))]
+mod tests {
+	use super::*;
+
+	use crate::storage::Key;
+
+	fn new_pool(capacity: u32) -> ChunkPool {
+		ChunkPool::new(unsafe {
+			RawChunk::new_unchecked(Key([0x42; 32]), capacity)
+		})
+	}
+
+	#[test]
+	fn alloc_grows_from_watermark() {
+		const CAPACITY: u32 = 5;
+
+		let mut pool = new_pool(CAPACITY);
+
+		// Cell `0` is reserved, so allocation starts at `1`.
+		for i in 1..CAPACITY {
+			assert_eq!(pool.alloc(), Ok(i));
+		}
+
+		// The pool is exhausted once every non-header cell is allocated.
+		assert!(pool.alloc().is_err());
+	}
+
+	#[test]
+	fn freed_cells_are_reused_before_growing() {
+		const CAPACITY: u32 = 5;
+
+		let mut pool = new_pool(CAPACITY);
+
+		let _a = pool.alloc().unwrap();
+		let b = pool.alloc().unwrap();
+		let c = pool.alloc().unwrap();
+
+		unsafe { pool.free(b) }.unwrap();
+
+		// The freed cell is handed back out before the watermark grows.
+		assert_eq!(pool.alloc(), Ok(b));
+		// The watermark carries on where it left off afterwards.
+		assert_eq!(pool.alloc(), Ok(c + 1));
+	}
+
+	#[test]
+	fn freeing_the_header_cell_is_rejected() {
+		const CAPACITY: u32 = 5;
+
+		let mut pool = new_pool(CAPACITY);
+
+		assert!(unsafe { pool.free(0) }.is_err());
+		// The header must be left untouched by the rejected call.
+		assert_eq!(pool.alloc(), Ok(1));
+	}
+
+	#[test]
+	fn double_freeing_the_most_recently_freed_cell_is_rejected() {
+		const CAPACITY: u32 = 5;
+
+		let mut pool = new_pool(CAPACITY);
+
+		let a = pool.alloc().unwrap();
+		unsafe { pool.free(a) }.unwrap();
+
+		// Freeing `a` again must not push it onto the free list twice.
+		assert!(unsafe { pool.free(a) }.is_err());
+
+		// `a` is handed back out exactly once, not twice, so the next
+		// allocation grows the watermark instead of re-aliasing `a`.
+		assert_eq!(pool.alloc(), Ok(a));
+		assert_ne!(pool.alloc(), Ok(a));
+	}
+
+	#[test]
+	fn malformed_header_cell_is_reported_as_an_error_not_a_panic() {
+		const CAPACITY: u32 = 5;
+
+		let mut pool = new_pool(CAPACITY);
+
+		// Cell `0` holding fewer bytes than an encoded header (e.g.
+		// foreign data from before this chunk became a pool) must be
+		// reported as a `ChunkError`, not panic on the short slice.
+		pool.chunk.store(0, &[0x01, 0x02, 0x03]).unwrap();
+
+		assert!(pool.alloc().is_err());
+	}
+}
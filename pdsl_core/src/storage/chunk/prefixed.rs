@@ -0,0 +1,294 @@
+use crate::{
+	storage::chunk::{
+		RawChunk,
+		error::{ChunkError, Result},
+	},
+};
+use std::collections::HashMap;
+
+/// Encodes a composite namespace as a sequence of length-prefixed
+/// segments, so that `["foo"]` and `["foob", "ar"]` never collide: each
+/// segment is preceded by its big-endian `u32` length before its bytes.
+fn encode_namespace(namespace: &[&[u8]]) -> Vec<u8> {
+	let mut bytes = Vec::new();
+	for segment in namespace {
+		bytes.extend_from_slice(&(segment.len() as u32).to_be_bytes());
+		bytes.extend_from_slice(segment);
+	}
+	bytes
+}
+
+/// Partitions a `RawChunk`'s cells into named, bounded sub-regions.
+///
+/// Translates a `(namespace, index)` pair into a single underlying
+/// cell. Namespaces are assigned disjoint `region_capacity`-sized
+/// windows in order of first use, recorded in a directory threaded
+/// through the chunk itself: cell `0` holds the number of registered
+/// namespaces, and each namespace occupies one directory cell storing
+/// its length-prefixed encoded bytes verbatim, immediately followed by
+/// its `region_capacity`-cell data window. Because a namespace's
+/// region is looked up by an exact byte-equality scan of the
+/// directory rather than a hash, the assignment is collision-free by
+/// construction, and every directory entry can be read back to
+/// recover the namespace it was registered for. Once a directory
+/// entry has been read, its `namespace -> region_start` mapping is
+/// memoized in-memory, so repeated access to the same namespace never
+/// rescans entries already seen.
+///
+/// # Guarantees
+///
+/// - `Owned`
+///
+/// Read more about kinds of guarantees and their effect [here](../../index.html#guarantees).
+pub struct PrefixedChunk {
+	/// The underlying raw chunk, shared by all namespaces.
+	chunk: RawChunk,
+	/// The number of cells reserved for each namespace's data window.
+	region_capacity: u32,
+	/// Memoized `namespace -> region_start` mappings for directory
+	/// entries already read from storage, keyed by encoded namespace.
+	directory: HashMap<Vec<u8>, u32>,
+}
+
+impl PrefixedChunk {
+	/// Creates a new prefixed chunk on top of the given raw chunk, with
+	/// each namespace reserving a window of `region_capacity` cells.
+	///
+	/// Registration of a namespace's region is deferred until it is
+	/// first written to; `new` itself never touches storage. If the
+	/// chunk's capacity cannot fit even a single namespace's directory
+	/// entry and data window, the first `store` into a new namespace
+	/// returns a `ChunkError` rather than panicking.
+	pub fn new(chunk: RawChunk, region_capacity: u32) -> Self {
+		Self{ chunk, region_capacity, directory: HashMap::new() }
+	}
+
+	/// The number of cells one registered namespace occupies: one
+	/// directory cell plus its data window.
+	fn stride(&self) -> u32 {
+		1 + self.region_capacity
+	}
+
+	/// Reads the number of namespaces registered so far from the header.
+	fn read_count(&self) -> Result<u32> {
+		Ok(match self.chunk.load(0)? {
+			Some(bytes) if bytes.len() >= 4 => {
+				let mut buf = [0_u8; 4];
+				buf.copy_from_slice(&bytes[0..4]);
+				u32::from_be_bytes(buf)
+			}
+			_ => 0,
+		})
+	}
+
+	/// Writes the number of registered namespaces back to the header.
+	fn write_count(&mut self, count: u32) -> Result<()> {
+		self.chunk.store(0, &count.to_be_bytes())
+	}
+
+	/// Scans the directory for `encoded`, returning the start of its
+	/// data window if it has already been registered.
+	///
+	/// Checks the in-memory directory cache first. On a miss, only
+	/// scans the entries not yet memoized — every entry ahead of those
+	/// was cached by an earlier call — and memoizes every entry read
+	/// along the way, not just a match, so later lookups for any of
+	/// them are also served without touching storage.
+	fn find_region(&mut self, encoded: &[u8]) -> Result<Option<u32>> {
+		if let Some(&region_start) = self.directory.get(encoded) {
+			return Ok(Some(region_start))
+		}
+		let stride = self.stride();
+		let count = self.read_count()?;
+		let scanned = self.directory.len() as u32;
+		let mut found = None;
+		for i in scanned..count {
+			let entry_cell = 1 + i * stride;
+			if let Some(bytes) = self.chunk.load(entry_cell)? {
+				let region_start = entry_cell + 1;
+				if bytes == encoded {
+					found = Some(region_start);
+				}
+				self.directory.insert(bytes, region_start);
+			}
+		}
+		Ok(found)
+	}
+
+	/// Registers `encoded` as a new namespace, returning the start of
+	/// its freshly reserved data window.
+	fn register_region(&mut self, encoded: &[u8]) -> Result<u32> {
+		let stride = self.stride();
+		let count = self.read_count()?;
+		let entry_cell = 1 + count * stride;
+		let region_start = entry_cell + 1;
+		let region_end = region_start + self.region_capacity;
+		if region_end > self.chunk.capacity() {
+			return Err(ChunkError::access_out_of_bounds(region_end, self.chunk.capacity()))
+		}
+		self.chunk.store(entry_cell, encoded)?;
+		self.write_count(count + 1)?;
+		self.directory.insert(encoded.to_vec(), region_start);
+		Ok(region_start)
+	}
+
+	/// Returns an error if `index` is outside of a namespace's
+	/// `region_capacity`-sized data window.
+	fn check_index(&self, index: u32) -> Result<()> {
+		if index >= self.region_capacity {
+			return Err(ChunkError::access_out_of_bounds(index, self.region_capacity))
+		}
+		Ok(())
+	}
+
+	/// Loads the bytes stored at `index` within `namespace`.
+	///
+	/// Returns `Ok(None)` without registering `namespace` if it has
+	/// never been written to.
+	pub fn load(&mut self, namespace: &[&[u8]], index: u32) -> Result<Option<Vec<u8>>> {
+		self.check_index(index)?;
+		let encoded = encode_namespace(namespace);
+		match self.find_region(&encoded)? {
+			Some(start) => self.chunk.load(start + index),
+			None => Ok(None),
+		}
+	}
+
+	/// Stores the given bytes at `index` within `namespace`.
+	///
+	/// Registers `namespace` with a fresh data window on first use.
+	pub fn store(&mut self, namespace: &[&[u8]], index: u32, bytes: &[u8]) -> Result<()> {
+		self.check_index(index)?;
+		let encoded = encode_namespace(namespace);
+		let start = match self.find_region(&encoded)? {
+			Some(start) => start,
+			None => self.register_region(&encoded)?,
+		};
+		self.chunk.store(start + index, bytes)
+	}
+
+	/// Removes the bytes stored at `index` within `namespace`.
+	///
+	/// A no-op if `namespace` has never been written to.
+	pub fn clear(&mut self, namespace: &[&[u8]], index: u32) -> Result<()> {
+		self.check_index(index)?;
+		let encoded = encode_namespace(namespace);
+		match self.find_region(&encoded)? {
+			Some(start) => self.chunk.clear(start + index),
+			None => Ok(()),
+		}
+	}
+}
+
+#[cfg(all(test, feature = "test-env"))]
+mod tests {
+	use super::*;
+
+	use crate::{
+		env::TestEnv,
+		storage::Key,
+	};
+
+	fn new_chunk(capacity: u32, region_capacity: u32) -> PrefixedChunk {
+		PrefixedChunk::new(
+			unsafe { RawChunk::new_unchecked(Key([0x42; 32]), capacity) },
+			region_capacity,
+		)
+	}
+
+	#[test]
+	fn composite_encoding_is_collision_free() {
+		let a = encode_namespace(&[b"foo"]);
+		let b = encode_namespace(&[b"foob", b"ar"]);
+		assert_ne!(a, b);
+	}
+
+	#[test]
+	fn namespaces_are_isolated_across_their_whole_region() {
+		const CAPACITY: u32 = 1000;
+		const REGION_CAPACITY: u32 = 10;
+
+		let mut chunk = new_chunk(CAPACITY, REGION_CAPACITY);
+
+		// Fill every index of two distinct namespaces' regions.
+		for i in 0..REGION_CAPACITY {
+			chunk.store(&[b"foo"], i, &[0xFF; 4]).unwrap();
+			chunk.store(&[b"bar"], i, &[0xAA; 4]).unwrap();
+		}
+
+		// Each namespace's region must still hold only what was stored
+		// into it, across the whole index range, not just index `0`.
+		for i in 0..REGION_CAPACITY {
+			assert_eq!(chunk.load(&[b"foo"], i), Ok(Some(vec![0xFF; 4])));
+			assert_eq!(chunk.load(&[b"bar"], i), Ok(Some(vec![0xAA; 4])));
+		}
+
+		chunk.clear(&[b"foo"], 0).unwrap();
+		assert_eq!(chunk.load(&[b"foo"], 0), Ok(None));
+		assert_eq!(chunk.load(&[b"bar"], 0), Ok(Some(vec![0xAA; 4])));
+	}
+
+	#[test]
+	fn index_out_of_region_is_rejected() {
+		const CAPACITY: u32 = 1000;
+		const REGION_CAPACITY: u32 = 10;
+
+		let mut chunk = new_chunk(CAPACITY, REGION_CAPACITY);
+
+		assert!(chunk.load(&[b"foo"], REGION_CAPACITY).is_err());
+	}
+
+	#[test]
+	fn assignment_is_exact_not_probabilistic() {
+		const CAPACITY: u32 = 1000;
+		const REGION_CAPACITY: u32 = 10;
+
+		let mut chunk = new_chunk(CAPACITY, REGION_CAPACITY);
+
+		// Registering many distinct namespaces must never let one
+		// namespace's writes leak into another's.
+		let namespaces: Vec<Vec<u8>> = (0..50_u32).map(|i| i.to_be_bytes().to_vec()).collect();
+		for (i, ns) in namespaces.iter().enumerate() {
+			chunk.store(&[ns.as_slice()], 0, &(i as u32).to_be_bytes()).unwrap();
+		}
+		for (i, ns) in namespaces.iter().enumerate() {
+			assert_eq!(chunk.load(&[ns.as_slice()], 0), Ok(Some((i as u32).to_be_bytes().to_vec())));
+		}
+	}
+
+	#[test]
+	fn exhaustion_is_reported_as_an_error() {
+		const REGION_CAPACITY: u32 = 10;
+		// Only enough room for the header and a single namespace's
+		// directory cell and region.
+		const CAPACITY: u32 = 1 + 1 + REGION_CAPACITY;
+
+		let mut chunk = new_chunk(CAPACITY, REGION_CAPACITY);
+
+		chunk.store(&[b"foo"], 0, b"fits").unwrap();
+		assert!(chunk.store(&[b"bar"], 0, b"does not fit").is_err());
+	}
+
+	#[test]
+	fn repeated_access_reuses_the_cached_directory_entry() {
+		const CAPACITY: u32 = 1000;
+		const REGION_CAPACITY: u32 = 10;
+
+		let mut chunk = new_chunk(CAPACITY, REGION_CAPACITY);
+
+		chunk.store(&[b"foo"], 0, b"test").unwrap();
+		let reads_after_registration = TestEnv::total_reads();
+
+		// Further access to the same namespace must be served from the
+		// memoized directory entry, without rescanning any of it: the
+		// only reads left are the one data cell read of each `load`.
+		for i in 1..REGION_CAPACITY {
+			chunk.store(&[b"foo"], i, b"test").unwrap();
+			chunk.load(&[b"foo"], i).unwrap();
+		}
+		assert_eq!(
+			TestEnv::total_reads(),
+			reads_after_registration + (REGION_CAPACITY - 1) as u64,
+		);
+	}
+}
@@ -131,6 +131,108 @@ impl RawChunk {
 	}
 }
 
+/// A chunk of raw cells whose capacity `N` is fixed at compile time.
+///
+/// Behaves like `RawChunk` but encodes its capacity in the type instead
+/// of storing it as a runtime `NonZeroU32`, so `capacity()` becomes a
+/// `const fn` and out-of-bounds access through a const generic index
+/// is rejected at compile time rather than returning a `ChunkError`.
+///
+/// # Guarantees
+///
+/// - `Owned`
+///
+/// Read more about kinds of guarantees and their effect [here](../index.html#guarantees).
+#[derive(Debug, PartialEq, Eq)]
+pub struct RawChunkN<const N: u32> {
+	/// The key to the associated constract storage slot.
+	key: Key,
+	/// Marker that prevents this type from being `Copy` or `Clone` by accident.
+	non_clone: NonCloneMarker<()>,
+}
+
+impl<const N: u32> RawChunkN<N> {
+	/// Fails to compile if `N` is zero.
+	const ASSERT_NONZERO: () = assert!(N > 0, "chunk capacity `N` must be non-zero");
+
+	/// Creates a new raw cell chunk with compile-time capacity `N` for the given key.
+	///
+	/// # Safety
+	///
+	/// This is unsafe since it does not check if the associated
+	/// contract storage does not alias with other accesses.
+	pub unsafe fn new_unchecked(key: Key) -> Self {
+		let _ = Self::ASSERT_NONZERO;
+		Self{
+			key,
+			non_clone: NonCloneMarker::default(),
+		}
+	}
+
+	/// Returns the capacity of this chunk.
+	pub const fn capacity() -> u32 {
+		N
+	}
+
+	/// Returns a key for the `n`-th cell if within bounds.
+	///
+	/// # Error
+	///
+	/// Returns an error if `n` is not within bounds.
+	fn offset_key(&self, n: u32) -> Result<Key> {
+		if n >= N {
+			return Err(ChunkError::access_out_of_bounds(n, N))
+		}
+		Ok(Key::with_offset(self.key, n))
+	}
+
+	/// Returns an accessor to the cell at the const generic index `I`.
+	///
+	/// Unlike `RawChunk::cell_at`, the bounds check against the chunk's
+	/// capacity `N` collapses entirely at compile time.
+	pub(crate) fn cell_at<const I: u32>(&mut self) -> RawChunkCell {
+		struct BoundsCheck<const I: u32, const N: u32>;
+		impl<const I: u32, const N: u32> BoundsCheck<I, N> {
+			const ASSERT: () = assert!(I < N, "cell index `I` is out of bounds");
+		}
+		let _ = BoundsCheck::<I, N>::ASSERT;
+		unsafe {
+			RawChunkCell::new_unchecked(Key::with_offset(self.key, I))
+		}
+	}
+
+	/// Loads the bytes stored in the `n`-th cell.
+	pub fn load(&self, n: u32) -> Result<Option<Vec<u8>>> {
+		self
+			.offset_key(n)
+			.map(|key| ContractEnv::load(key))
+	}
+
+	/// Stores the given bytes into the `n`-th cell.
+	pub fn store(&mut self, n: u32, bytes: &[u8]) -> Result<()> {
+		self
+			.offset_key(n)
+			.map(|key| ContractEnv::store(key, bytes))
+	}
+
+	/// Removes the bytes stored in the `n`-th cell.
+	pub fn clear(&mut self, n: u32) -> Result<()> {
+		self
+			.offset_key(n)
+			.map(|key| ContractEnv::clear(key))
+	}
+}
+
+/// Converts a compile-time sized chunk into a dynamically sized one, so
+/// code that is generic over the capacity can operate on either kind.
+impl<const N: u32> From<RawChunkN<N>> for RawChunk {
+	fn from(chunk: RawChunkN<N>) -> Self {
+		unsafe {
+			RawChunk::new_unchecked(chunk.key, N)
+		}
+	}
+}
+
 #[cfg(all(test, feature = "test-env"))]
 mod tests {
 	use super::*;
@@ -226,4 +328,32 @@ mod tests {
 		assert_eq!(TestEnv::total_reads(), CAPACITY as u64 + LOAD_REPEATS as u64);
 		assert_eq!(TestEnv::total_writes(), CAPACITY as u64 + STORE_REPEATS as u64);
 	}
+
+	#[test]
+	fn raw_chunk_n_simple() {
+		const WORD_SIZE: usize = 4;
+
+		let mut chunk = unsafe {
+			RawChunkN::<5>::new_unchecked(Key([0x42; 32]))
+		};
+
+		// Capacity is known at compile time.
+		assert_eq!(RawChunkN::<5>::capacity(), 5);
+		for i in 0..RawChunkN::<5>::capacity() {
+			assert_eq!(chunk.load(i), Ok(None));
+		}
+		// Out of bounds load.
+		assert!(chunk.load(RawChunkN::<5>::capacity()).is_err());
+
+		// Store some elements.
+		for i in 0..RawChunkN::<5>::capacity() {
+			assert!(chunk.store(i, &[i as u8; WORD_SIZE]).is_ok());
+			assert_eq!(chunk.load(i), Ok(Some(vec![i as u8; WORD_SIZE])));
+		}
+
+		// Converting into a dynamically sized chunk preserves capacity and state.
+		let dyn_chunk: RawChunk = chunk.into();
+		assert_eq!(dyn_chunk.capacity(), 5);
+		assert_eq!(dyn_chunk.load(0), Ok(Some(vec![0; WORD_SIZE])));
+	}
 }
\ No newline at end of file